@@ -4,12 +4,66 @@ use std::sync::{
     Arc,
     atomic::{AtomicBool, Ordering},
 };
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+/// the result of a timed wait, indicating whether it returned due to a timeout
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WaitTimeoutResult(bool);
+
+impl WaitTimeoutResult {
+    /// returns true if the wait returned because the timeout elapsed
+    pub fn timed_out(&self) -> bool {
+        self.0
+    }
+}
 
 /// sets the ready flag and notifies a waiting thread via condvar
 #[derive(Clone)]
 pub struct Notifier {
     ready: Arc<AtomicBool>,
     cvar: Arc<Condvar>,
+    wakers: Arc<Mutex<Vec<Waker>>>,
+}
+
+/// the outcome of an interruptible wait
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WaitResult {
+    /// the ready flag was set
+    Ready,
+    /// the wait was cancelled before the flag was set
+    Cancelled,
+}
+
+/// cancels blocked interruptible waiters sharing the flag
+#[derive(Clone)]
+pub struct Canceller {
+    cancelled: Arc<AtomicBool>,
+    cvar: Arc<Condvar>,
+    mutex: Arc<Mutex<()>>,
+}
+
+impl Canceller {
+    /// creates a new canceller from shared cancelled flag, condvar, and mutex
+    const fn new(cancelled: Arc<AtomicBool>, cvar: Arc<Condvar>, mutex: Arc<Mutex<()>>) -> Self {
+        Self {
+            cancelled,
+            cvar,
+            mutex,
+        }
+    }
+
+    /// sets the cancelled flag and wakes all waiting threads
+    pub fn cancel(&self) {
+        // serialize with `wait_interruptible` so a cancel between its
+        // `cancelled` check and parking can't be lost
+        let guard = self.mutex.lock();
+        self.cancelled.store(true, Ordering::Release);
+        drop(guard);
+        self.cvar.notify_all();
+    }
 }
 
 /// sets the ready flag without notifying
@@ -49,15 +103,38 @@ impl Spectator {
 }
 
 impl Notifier {
-    /// creates a new notifier from shared ready and condvar
-    const fn new(ready: Arc<AtomicBool>, cvar: Arc<Condvar>) -> Self {
-        Self { ready, cvar }
+    /// creates a new notifier from shared ready, condvar, and wakers
+    const fn new(
+        ready: Arc<AtomicBool>,
+        cvar: Arc<Condvar>,
+        wakers: Arc<Mutex<Vec<Waker>>>,
+    ) -> Self {
+        Self {
+            ready,
+            cvar,
+            wakers,
+        }
+    }
+
+    /// wakes every async task registered on the shared flag
+    fn wake_tasks(&self) {
+        for waker in self.wakers.lock().drain(..) {
+            waker.wake();
+        }
     }
 
-    /// sets the ready flag and notifies one waiting thread
+    /// sets the ready flag and wakes exactly one waiting thread
     pub fn notify(&self) {
         self.ready.store(true, Ordering::Release);
         self.cvar.notify_one();
+        self.wake_tasks();
+    }
+
+    /// sets the ready flag and wakes all waiting threads
+    pub fn notify_all(&self) {
+        self.ready.store(true, Ordering::Release);
+        self.cvar.notify_all();
+        self.wake_tasks();
     }
 }
 
@@ -66,7 +143,9 @@ impl Notifier {
 pub struct Waiter {
     ready: Arc<AtomicBool>,
     cvar: Arc<Condvar>,
-    mutex: Mutex<()>,
+    mutex: Arc<Mutex<()>>,
+    wakers: Arc<Mutex<Vec<Waker>>>,
+    cancelled: Arc<AtomicBool>,
 }
 
 impl Waiter {
@@ -95,9 +174,80 @@ impl Waiter {
         }
     }
 
+    /// waits until the ready flag is true or `dur` elapses
+    pub fn wait_timeout(&self, dur: Duration) -> WaitTimeoutResult {
+        self.wait_deadline(Instant::now() + dur)
+    }
+
+    /// waits until the ready flag is true or `deadline` is reached
+    pub fn wait_deadline(&self, deadline: Instant) -> WaitTimeoutResult {
+        let backoff = Backoff::new();
+
+        loop {
+            if self.ready.load(Ordering::Acquire) {
+                return WaitTimeoutResult(false);
+            }
+
+            if backoff.is_completed() {
+                break;
+            }
+            backoff.snooze();
+        }
+
+        let mut guard = self.mutex.lock();
+        loop {
+            if self.ready.load(Ordering::Acquire) {
+                return WaitTimeoutResult(false);
+            }
+
+            let result = self.cvar.wait_until(&mut guard, deadline);
+            if result.timed_out() && !self.ready.load(Ordering::Acquire) {
+                return WaitTimeoutResult(true);
+            }
+        }
+    }
+
     /// returns a notifier handle for setting and notifying
     pub fn notifier(&self) -> Notifier {
-        Notifier::new(self.ready.clone(), self.cvar.clone())
+        Notifier::new(self.ready.clone(), self.cvar.clone(), self.wakers.clone())
+    }
+
+    /// returns a future that resolves once the ready flag is true
+    pub fn wait_async(&self) -> WaiterFuture {
+        WaiterFuture {
+            ready: self.ready.clone(),
+            wakers: self.wakers.clone(),
+        }
+    }
+
+    /// waits until the ready flag is true or the wait is cancelled
+    pub fn wait_interruptible(&self) -> WaitResult {
+        let backoff = Backoff::new();
+
+        loop {
+            if self.ready.load(Ordering::Acquire) {
+                return WaitResult::Ready;
+            }
+            if self.cancelled.load(Ordering::Acquire) {
+                return WaitResult::Cancelled;
+            }
+
+            if backoff.is_completed() {
+                let mut guard = self.mutex.lock();
+                if !self.ready.load(Ordering::Acquire)
+                    && !self.cancelled.load(Ordering::Acquire)
+                {
+                    self.cvar.wait(&mut guard);
+                }
+            } else {
+                backoff.snooze();
+            }
+        }
+    }
+
+    /// returns a canceller handle for aborting interruptible waits
+    pub fn canceller(&self) -> Canceller {
+        Canceller::new(self.cancelled.clone(), self.cvar.clone(), self.mutex.clone())
     }
 
     /// returns a setter handle for setting only
@@ -115,3 +265,122 @@ impl Waiter {
         self.ready.store(false, Ordering::Release)
     }
 }
+
+/// updates shared state under the lock and notifies waiting threads
+pub struct NotifierOn<T> {
+    state: Arc<Mutex<T>>,
+    cvar: Arc<Condvar>,
+}
+
+impl<T> Clone for NotifierOn<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            cvar: self.cvar.clone(),
+        }
+    }
+}
+
+impl<T> NotifierOn<T> {
+    /// creates a new notifier from shared state and condvar
+    const fn new(state: Arc<Mutex<T>>, cvar: Arc<Condvar>) -> Self {
+        Self { state, cvar }
+    }
+
+    /// mutates the shared state under the lock then wakes all waiters
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        let mut guard = self.state.lock();
+        f(&mut guard);
+        drop(guard);
+        self.cvar.notify_all();
+    }
+}
+
+/// waits on a user predicate over arbitrary shared state `T`
+pub struct WaiterOn<T> {
+    state: Arc<Mutex<T>>,
+    cvar: Arc<Condvar>,
+}
+
+impl<T: Default> Default for WaiterOn<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> WaiterOn<T> {
+    /// creates a new waiter wrapping the given initial state
+    pub fn new(state: T) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(state)),
+            cvar: Arc::new(Condvar::new()),
+        }
+    }
+
+    /// waits until `pred` evaluated on the shared state returns true
+    pub fn wait_until<F: Fn(&T) -> bool>(&self, pred: F) {
+        let backoff = Backoff::new();
+
+        loop {
+            {
+                let guard = self.state.lock();
+                if pred(&guard) {
+                    break;
+                }
+            }
+
+            if backoff.is_completed() {
+                let mut guard = self.state.lock();
+                while !pred(&guard) {
+                    self.cvar.wait(&mut guard);
+                }
+                break;
+            } else {
+                backoff.snooze();
+            }
+        }
+    }
+
+    /// waits while `pred` evaluated on the shared state returns true
+    pub fn wait_while<F: Fn(&T) -> bool>(&self, pred: F) {
+        self.wait_until(|state| !pred(state));
+    }
+
+    /// returns a notifier handle for updating the shared state
+    pub fn notifier(&self) -> NotifierOn<T> {
+        NotifierOn::new(self.state.clone(), self.cvar.clone())
+    }
+}
+
+/// resolves once the shared ready flag is set, waking via the notify paths
+pub struct WaiterFuture {
+    ready: Arc<AtomicBool>,
+    wakers: Arc<Mutex<Vec<Waker>>>,
+}
+
+impl Future for WaiterFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.ready.load(Ordering::Acquire) {
+            return Poll::Ready(());
+        }
+
+        // register before the final re-check so a notify between the two
+        // loads can't be lost; skip the push when an equivalent waker is
+        // already registered instead of piling up a fresh clone on every
+        // re-poll (a waker that no longer matches is left in place)
+        {
+            let mut wakers = self.wakers.lock();
+            if !wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                wakers.push(cx.waker().clone());
+            }
+        }
+
+        if self.ready.load(Ordering::Acquire) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}